@@ -0,0 +1,493 @@
+//! Chain-interaction layer: the connection pool plus the low-level reads and
+//! submits the `register` module builds on.
+//!
+//! Everything that touches the network lives here so that `register` can stay
+//! focused on registration strategy. Runtime access uses subxt's dynamic API
+//! because the tool ships without generated metadata.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Context, Result};
+use futures::StreamExt;
+use primitive_types::{H256, U256};
+use sp_core::crypto::Ss58Codec;
+use subxt::config::PolkadotConfig;
+use subxt::dynamic::Value;
+use subxt::OnlineClient;
+use tokio::sync::{mpsc, RwLock};
+use tokio::time::{sleep, timeout};
+
+use crate::constants::*;
+
+/// A SCALE-encoded, signed extrinsic ready to be broadcast.
+#[derive(Clone)]
+pub struct SignedExtrinsic {
+    pub bytes: Vec<u8>,
+    pub hash: H256,
+}
+
+impl SignedExtrinsic {
+    pub fn from_bytes(bytes: Vec<u8>) -> Self {
+        let hash = H256(sp_core::blake2_256(&bytes));
+        Self { bytes, hash }
+    }
+
+    /// 0x-prefixed hex of the raw SCALE bytes.
+    pub fn to_hex(&self) -> String {
+        format!("0x{}", hex::encode(&self.bytes))
+    }
+
+    pub fn from_hex(s: &str) -> Result<Self> {
+        let raw = s.strip_prefix("0x").unwrap_or(s);
+        let bytes = hex::decode(raw).context("extrinsic is not valid hex")?;
+        Ok(Self::from_bytes(bytes))
+    }
+}
+
+/// Minimal block identity used by the PoW solver and the staleness guard.
+#[derive(Clone, Copy)]
+pub struct TipBlock {
+    pub number: u64,
+    pub hash: H256,
+}
+
+/// Timing/lag snapshot captured while probing an endpoint.
+#[derive(Clone)]
+pub struct EndpointHealth {
+    pub url: String,
+    pub latency: Duration,
+    pub best_block: u64,
+    pub lag: u64,
+}
+
+impl EndpointHealth {
+    /// Lower is better: round-trip latency in milliseconds plus a penalty of
+    /// 500 per block of lag, so each block behind the tip weighs as much as
+    /// 500ms of extra latency.
+    pub fn score(&self) -> f64 {
+        self.latency.as_secs_f64() * 1_000.0 + self.lag as f64 * 500.0
+    }
+}
+
+/// A single live connection plus its most recent health reading.
+struct Endpoint {
+    url: String,
+    api: OnlineClient<PolkadotConfig>,
+    health: EndpointHealth,
+}
+
+/// A ranked set of endpoints with transparent failover. Reads are routed to the
+/// healthiest node; the rest are kept as hot standbys and promoted when the
+/// primary drops its connection or falls more than `max_lag_blocks` behind the
+/// chain tip.
+pub struct ConnectionPool {
+    endpoints: RwLock<Vec<Endpoint>>,
+    primary: AtomicUsize,
+    max_lag_blocks: u64,
+    submit_timeout: Duration,
+}
+
+impl ConnectionPool {
+    /// Probe every endpoint, rank them best-first, and build the pool.
+    ///
+    /// Probing makes up to [`MAX_RPC_CALL_RETRIES`] connection attempts per endpoint,
+    /// mirroring the `poll_get_latest_blockhash` loop in the reference
+    /// accounts-cluster-bench.
+    pub async fn connect(
+        urls: Vec<String>,
+        submit_timeout: Duration,
+        max_lag_blocks: u64,
+    ) -> Result<Self> {
+        let mut endpoints = Vec::with_capacity(urls.len());
+        let mut tip = 0u64;
+        for url in &urls {
+            match Self::probe(url).await {
+                Ok((api, health)) => {
+                    tip = tip.max(health.best_block);
+                    endpoints.push(Endpoint { url: url.clone(), api, health });
+                }
+                Err(e) => log::warn!("endpoint {url} failed to probe: {e:#}"),
+            }
+        }
+        if endpoints.is_empty() {
+            return Err(anyhow!("no usable RPC endpoints among {} candidates", urls.len()));
+        }
+
+        // Recompute lag against the best tip observed across all endpoints and
+        // rank healthiest-first.
+        for ep in endpoints.iter_mut() {
+            ep.health.lag = tip.saturating_sub(ep.health.best_block);
+        }
+        endpoints.sort_by(|a, b| a.health.score().total_cmp(&b.health.score()));
+        for (i, ep) in endpoints.iter().enumerate() {
+            log::info!(
+                "endpoint #{i} {} — {:.0}ms, block {}, lag {}",
+                ep.url,
+                ep.health.latency.as_secs_f64() * 1_000.0,
+                ep.health.best_block,
+                ep.health.lag
+            );
+        }
+
+        Ok(Self {
+            endpoints: RwLock::new(endpoints),
+            primary: AtomicUsize::new(0),
+            max_lag_blocks,
+            submit_timeout,
+        })
+    }
+
+    async fn probe(url: &str) -> Result<(OnlineClient<PolkadotConfig>, EndpointHealth)> {
+        let mut last_err = None;
+        for attempt in 0..MAX_RPC_CALL_RETRIES {
+            let started = Instant::now();
+            match OnlineClient::<PolkadotConfig>::from_url(url).await {
+                Ok(api) => {
+                    let best_block = api.blocks().at_latest().await?.number().into();
+                    return Ok((
+                        api,
+                        EndpointHealth {
+                            url: url.to_string(),
+                            latency: started.elapsed(),
+                            best_block,
+                            lag: 0,
+                        },
+                    ));
+                }
+                Err(e) => {
+                    last_err = Some(e);
+                    sleep(Duration::from_millis(200 * (attempt as u64 + 1))).await;
+                }
+            }
+        }
+        Err(anyhow!(last_err.expect("loop ran at least once")))
+    }
+
+    pub async fn api(&self) -> OnlineClient<PolkadotConfig> {
+        let idx = self.primary.load(Ordering::Relaxed);
+        let eps = self.endpoints.read().await;
+        eps[idx.min(eps.len() - 1)].api.clone()
+    }
+
+    /// Promote the next-best standby to primary. Called on a dropped WSS
+    /// connection or when the current primary lags past `max_lag_blocks`.
+    pub async fn failover(&self) -> Result<()> {
+        let eps = self.endpoints.read().await;
+        let current = self.primary.load(Ordering::Relaxed);
+        let next = current + 1;
+        if next >= eps.len() {
+            return Err(anyhow!("all {} endpoints exhausted during failover", eps.len()));
+        }
+        log::warn!("failing over from {} to {}", eps[current].url, eps[next].url);
+        self.primary.store(next, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Best block height seen across every endpoint in the pool — the chain
+    /// tip to measure the primary's lag against.
+    async fn pool_best_block(&self) -> u64 {
+        let eps = self.endpoints.read().await;
+        let mut best = 0u64;
+        for ep in eps.iter() {
+            if let Ok(block) = ep.api.blocks().at_latest().await {
+                best = best.max(block.number().into());
+            }
+        }
+        best
+    }
+
+    /// Current best block on the primary, failing over if it drops or has
+    /// fallen more than `max_lag_blocks` behind the pool-wide chain tip.
+    pub async fn tip_block(&self) -> Result<TipBlock> {
+        loop {
+            let api = self.api().await;
+            match api.blocks().at_latest().await {
+                Ok(block) => {
+                    let number: u64 = block.number().into();
+                    // Compare against the tip sampled across the other nodes,
+                    // not this primary's own history (which is always ~current).
+                    let pool_tip = self.pool_best_block().await;
+                    if pool_tip.saturating_sub(number) > self.max_lag_blocks {
+                        log::warn!(
+                            "primary at {number} lags pool tip {pool_tip} by more than {}",
+                            self.max_lag_blocks
+                        );
+                        self.failover().await?;
+                        continue;
+                    }
+                    return Ok(TipBlock { number, hash: H256(block.hash().0) });
+                }
+                Err(e) => {
+                    log::warn!("tip read failed: {e:#}");
+                    self.failover().await?;
+                }
+            }
+        }
+    }
+
+    /// Read the subnet's current registration difficulty.
+    pub async fn difficulty(&self, netuid: u16) -> Result<U256> {
+        let api = self.api().await;
+        let query = subxt::dynamic::storage(
+            SUBTENSOR_PALLET,
+            DIFFICULTY_STORAGE,
+            vec![Value::u128(netuid as u128)],
+        );
+        let raw = api
+            .storage()
+            .at_latest()
+            .await?
+            .fetch(&query)
+            .await?
+            .ok_or_else(|| anyhow!("subnet {netuid} has no Difficulty entry"))?;
+        Ok(U256::from(raw.to_value()?.as_u128().unwrap_or(1).max(1)))
+    }
+
+    pub fn max_lag_blocks(&self) -> u64 {
+        self.max_lag_blocks
+    }
+
+    pub fn submit_timeout(&self) -> Duration {
+        self.submit_timeout
+    }
+
+    /// URLs of every endpoint in the pool, primary first.
+    pub async fn urls(&self) -> Vec<String> {
+        self.endpoints.read().await.iter().map(|e| e.url.clone()).collect()
+    }
+
+    /// `(url, api)` for every endpoint, primary first, so callers can address a
+    /// specific node rather than always hitting the primary.
+    pub async fn clients(&self) -> Vec<(String, OnlineClient<PolkadotConfig>)> {
+        self.endpoints
+            .read()
+            .await
+            .iter()
+            .map(|e| (e.url.clone(), e.api.clone()))
+            .collect()
+    }
+
+    /// Snapshot of every endpoint's last health reading, best-first.
+    pub async fn health(&self) -> Vec<EndpointHealth> {
+        self.endpoints.read().await.iter().map(|e| e.health.clone()).collect()
+    }
+
+    /// Broadcast a signed extrinsic through a specific endpoint's client.
+    pub async fn submit_via(
+        api: &OnlineClient<PolkadotConfig>,
+        ext: &SignedExtrinsic,
+        submit_timeout: Duration,
+    ) -> Result<H256> {
+        timeout(submit_timeout, api.backend().submit_transaction(&ext.bytes))
+            .await
+            .map_err(|_| anyhow!("submit timed out"))?
+            .context("submit_transaction failed")?;
+        Ok(ext.hash)
+    }
+
+    /// Check whether `ext` is present in the latest block seen by `api`.
+    pub async fn is_included_via(
+        api: &OnlineClient<PolkadotConfig>,
+        ext: &SignedExtrinsic,
+    ) -> Result<bool> {
+        let block = api.blocks().at_latest().await?;
+        for tx in block.extrinsics().await?.iter() {
+            if H256(sp_core::blake2_256(&tx?.bytes())) == ext.hash {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+}
+
+/// Per-endpoint delivery counters reported at the end of a submission round.
+#[derive(Default, Clone)]
+pub struct SubmissionCounters {
+    pub submitted: usize,
+    pub confirmed: usize,
+    pub timed_out: usize,
+}
+
+/// Outcome of a fan-out submission: the first endpoint to confirm inclusion
+/// plus the per-endpoint counters gathered along the way.
+pub struct InclusionReport {
+    pub endpoint: String,
+    pub extrinsic_hash: H256,
+    pub counters: HashMap<String, SubmissionCounters>,
+}
+
+/// Fans a signed extrinsic out to every endpoint at once and races them for
+/// inclusion, cancelling the losers as soon as the first node confirms.
+///
+/// Ported from the accounts-cluster-bench `TransactionExecutor` pattern: a set
+/// of in-flight `(endpoint, extrinsic_hash)` entries is polled for inclusion in
+/// a background task with a bounded retry count ([`MAX_RPC_CALL_RETRIES`]).
+pub struct SubmissionExecutor {
+    pool: Arc<ConnectionPool>,
+}
+
+impl SubmissionExecutor {
+    pub fn new(pool: Arc<ConnectionPool>) -> Self {
+        Self { pool }
+    }
+
+    pub async fn submit_all(&self, ext: &SignedExtrinsic) -> Result<InclusionReport> {
+        // Address each endpoint directly — one in-flight task per node — so the
+        // extrinsic actually fans out instead of hitting the primary N times.
+        let clients = self.pool.clients().await;
+        let counters: Arc<RwLock<HashMap<String, SubmissionCounters>>> =
+            Arc::new(RwLock::new(HashMap::new()));
+        let done = Arc::new(AtomicBool::new(false));
+        let (tx, mut rx) = mpsc::channel::<String>(clients.len().max(1));
+        let submit_timeout = self.pool.submit_timeout();
+
+        for (url, api) in clients {
+            let ext = ext.clone();
+            let counters = counters.clone();
+            let done = done.clone();
+            let tx = tx.clone();
+            tokio::spawn(async move {
+                counters.write().await.entry(url.clone()).or_default().submitted += 1;
+                let included = Self::submit_and_watch(&api, &ext, submit_timeout, &done).await;
+                let mut c = counters.write().await;
+                let entry = c.entry(url.clone()).or_default();
+                match included {
+                    Ok(true) => {
+                        entry.confirmed += 1;
+                        drop(c);
+                        if !done.swap(true, Ordering::SeqCst) {
+                            let _ = tx.send(url).await;
+                        }
+                    }
+                    Ok(false) | Err(_) => entry.timed_out += 1,
+                }
+            });
+        }
+        drop(tx);
+
+        let winner = rx
+            .recv()
+            .await
+            .ok_or_else(|| anyhow!("extrinsic {:?} not included by any endpoint", ext.hash))?;
+        let counters = counters.read().await.clone();
+        Ok(InclusionReport {
+            endpoint: winner,
+            extrinsic_hash: ext.hash,
+            counters,
+        })
+    }
+
+    async fn submit_and_watch(
+        api: &OnlineClient<PolkadotConfig>,
+        ext: &SignedExtrinsic,
+        submit_timeout: Duration,
+        done: &AtomicBool,
+    ) -> Result<bool> {
+        ConnectionPool::submit_via(api, ext, submit_timeout).await?;
+        let deadline = Instant::now() + submit_timeout;
+        let mut retries = 0usize;
+        while Instant::now() < deadline && !done.load(Ordering::SeqCst) {
+            match ConnectionPool::is_included_via(api, ext).await {
+                Ok(true) => return Ok(true),
+                Ok(false) => {}
+                Err(_) if retries < MAX_RPC_CALL_RETRIES => retries += 1,
+                Err(e) => return Err(e),
+            }
+            sleep(Duration::from_millis(500)).await;
+        }
+        Ok(false)
+    }
+}
+
+/// A registration-relevant change yielded by [`subscribe_registration_events`].
+pub enum RegistrationUpdate {
+    /// A hotkey was assigned a UID on a subnet.
+    NeuronRegistered { netuid: u16, hotkey: String, uid: u16 },
+}
+
+/// Open a finalized-head subscription and yield registration-relevant changes.
+///
+/// Mirrors the `PubsubClient` logs subscription in the reference bench: one
+/// long-lived stream over the existing WSS connection replaces repeated status
+/// polls. On a dropped connection the stream is transparently re-opened against
+/// the current primary endpoint.
+pub async fn subscribe_registration_events(
+    pool: Arc<ConnectionPool>,
+) -> Result<mpsc::Receiver<RegistrationUpdate>> {
+    let (tx, rx) = mpsc::channel(64);
+    tokio::spawn(async move {
+        loop {
+            let api = pool.api().await;
+            let mut blocks = match api.blocks().subscribe_finalized().await {
+                Ok(s) => s,
+                Err(e) => {
+                    log::warn!("finalized subscription failed: {e:#}");
+                    if pool.failover().await.is_err() {
+                        break;
+                    }
+                    continue;
+                }
+            };
+            while let Some(block) = blocks.next().await {
+                let block = match block {
+                    Ok(b) => b,
+                    Err(e) => {
+                        log::warn!("finalized head error: {e:#}");
+                        break; // re-subscribe against the next-best endpoint
+                    }
+                };
+                let Ok(events) = block.events().await else { continue };
+                for ev in events.iter().flatten() {
+                    if ev.pallet_name() == SUBTENSOR_PALLET
+                        && ev.variant_name() == NEURON_REGISTERED_EVENT
+                    {
+                        if let Some(update) = decode_neuron_registered(&ev) {
+                            if tx.send(update).await.is_err() {
+                                return; // consumer dropped; stop the stream
+                            }
+                        }
+                    }
+                }
+            }
+            // Stream ended (dropped connection): promote the next-best endpoint
+            // and re-subscribe, stopping only once every endpoint is exhausted.
+            if pool.failover().await.is_err() {
+                break;
+            }
+        }
+    });
+    Ok(rx)
+}
+
+fn decode_neuron_registered(
+    ev: &subxt::events::EventDetails<PolkadotConfig>,
+) -> Option<RegistrationUpdate> {
+    let fields = ev.field_values().ok()?;
+    let netuid = fields.at(0)?.as_u128()? as u16;
+    let uid = fields.at(1)?.as_u128()? as u16;
+    // The hotkey is a SCALE AccountId32 composite — SS58-encode it so it
+    // compares equal to the address the register commands hold.
+    let hotkey = account_ss58(fields.at(2)?)?;
+    Some(RegistrationUpdate::NeuronRegistered { netuid, hotkey, uid })
+}
+
+/// SS58-encode an `AccountId32` carried as a SCALE value (a composite of 32
+/// byte-valued primitives).
+fn account_ss58(v: &subxt::ext::scale_value::Value<u32>) -> Option<String> {
+    let mut bytes = Vec::with_capacity(32);
+    collect_bytes(v, &mut bytes);
+    let arr: [u8; 32] = bytes.try_into().ok()?;
+    Some(sp_core::crypto::AccountId32::from(arr).to_ss58check())
+}
+
+fn collect_bytes(v: &subxt::ext::scale_value::Value<u32>, out: &mut Vec<u8>) {
+    use subxt::ext::scale_value::{Primitive, ValueDef};
+    match &v.value {
+        ValueDef::Primitive(Primitive::U128(n)) => out.push(*n as u8),
+        ValueDef::Composite(c) => c.values().for_each(|f| collect_bytes(f, out)),
+        _ => {}
+    }
+}
@@ -0,0 +1,24 @@
+//! Shared pallet/storage identifiers and tuning constants used across the
+//! `client` and `register` modules.
+
+/// SubtensorModule pallet name on the Bittensor runtime.
+pub const SUBTENSOR_PALLET: &str = "SubtensorModule";
+
+/// Storage item holding the per-subnet registration PoW difficulty.
+pub const DIFFICULTY_STORAGE: &str = "Difficulty";
+
+/// `register` extrinsic (proof-of-work registration).
+pub const REGISTER_CALL: &str = "register";
+
+/// `burned_register` extrinsic (burn registration).
+pub const BURNED_REGISTER_CALL: &str = "burned_register";
+
+/// Event emitted once a neuron's UID is assigned on a subnet.
+pub const NEURON_REGISTERED_EVENT: &str = "NeuronRegistered";
+
+/// Upper bound on per-call RPC retries, mirrored from the reference
+/// accounts-cluster-bench executor.
+pub const MAX_RPC_CALL_RETRIES: usize = 5;
+
+/// Number of recent finalized blocks the tip oracle samples by default.
+pub const TIP_ORACLE_SAMPLE_BLOCKS: u64 = 30;
@@ -0,0 +1,52 @@
+//! Wallet and keypair loading from the local Bittensor wallet directory.
+
+use std::path::PathBuf;
+
+use anyhow::{anyhow, Context, Result};
+use sp_core::sr25519::Pair as Sr25519Pair;
+use sp_core::Pair;
+
+/// A loaded signing keypair together with its SS58 address.
+pub struct Keypair {
+    pub pair: Sr25519Pair,
+    pub ss58: String,
+}
+
+fn wallet_root() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(".bittensor")
+        .join("wallets")
+}
+
+/// Load a coldkey from `~/.bittensor/wallets/<wallet>/coldkey`.
+pub fn load_coldkey(wallet: &str) -> Result<Keypair> {
+    load_key(wallet, "coldkey")
+}
+
+/// Load a hotkey from `~/.bittensor/wallets/<wallet>/hotkeys/<hotkey>`.
+pub fn load_hotkey(wallet: &str, hotkey: &str) -> Result<Keypair> {
+    load_key(wallet, &format!("hotkeys/{hotkey}"))
+}
+
+fn load_key(wallet: &str, rel: &str) -> Result<Keypair> {
+    let path = wallet_root().join(wallet).join(rel);
+    let raw = std::fs::read_to_string(&path)
+        .with_context(|| format!("reading key file {}", path.display()))?;
+    let json: serde_json::Value =
+        serde_json::from_str(&raw).context("wallet key file is not valid JSON")?;
+    let secret = json
+        .get("secretSeed")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| anyhow!("no secretSeed in {}", path.display()))?;
+    let seed = hex::decode(secret.strip_prefix("0x").unwrap_or(secret))
+        .context("secretSeed is not valid hex")?;
+    let pair = Sr25519Pair::from_seed_slice(&seed)
+        .map_err(|e| anyhow!("invalid sr25519 seed: {e:?}"))?;
+    let ss58 = json
+        .get("ss58Address")
+        .and_then(|v| v.as_str())
+        .map(str::to_string)
+        .unwrap_or_default();
+    Ok(Keypair { pair, ss58 })
+}
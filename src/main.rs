@@ -27,6 +27,14 @@ struct Cli {
     )]
     rpc_url: Vec<String>,
 
+    /// Per-endpoint confirmation timeout for the concurrent submission engine (seconds)
+    #[arg(long, default_value_t = 30)]
+    submit_timeout_secs: u64,
+
+    /// Fail an endpoint over to the next-best node when it falls this many blocks behind the tip
+    #[arg(long, default_value_t = 4)]
+    max_lag_blocks: u64,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -53,6 +61,19 @@ enum Commands {
         #[arg(long, default_value_t = 64)]
         era_period: u64,
 
+        /// Build and sign the extrinsic offline and print the SCALE hex instead of broadcasting
+        #[arg(long, default_value_t = false)]
+        offline: bool,
+        /// Genesis hash override for offline signing (0x-prefixed)
+        #[arg(long)]
+        genesis_hash: Option<String>,
+        /// Mortal checkpoint block hash override for offline signing (0x-prefixed)
+        #[arg(long)]
+        block_hash: Option<String>,
+        /// Account nonce override for offline signing
+        #[arg(long)]
+        nonce: Option<u32>,
+
         /// Watch mempool for competing burned_register txs
         #[arg(long, default_value_t = false)]
         watch_mempool: bool,
@@ -69,9 +90,9 @@ enum Commands {
         #[arg(long, default_value_t = 1.25)]
         watch_bump_now: f64,
 
-        /// Optional tip in RAO (planck) to prioritize inclusion
+        /// Tip in RAO (planck), or "auto" to seed the first RBF round from the p75 on-chain tip oracle
         #[arg(long)]
-        tip: Option<u128>,
+        tip: Option<String>,
         /// RBF rounds (same nonce, higher tip)
         #[arg(long, default_value_t = 3)]
         rbf_rounds: u32,
@@ -81,9 +102,29 @@ enum Commands {
         /// Seconds to wait between RBF resubmissions
         #[arg(long, default_value_t = 6)]
         rbf_wait_secs: u64,
-        /// Optional tip in RAO (planck) to prioritize inclusion
+    },
+
+    /// Register to a subnet by solving the proof-of-work puzzle
+    PowRegister {
+        #[arg(short, long)]
+        subnet: u16,
+        #[arg(short, long)]
+        wallet: String,
+        #[arg(short = 'H', long)]
+        hotkey: String,
+        /// Number of solver threads (defaults to the number of CPUs)
         #[arg(long)]
-        tip: Option<u128>,
+        workers: Option<usize>,
+        /// Restart the search if the chain advances past this many blocks
+        #[arg(long, default_value_t = 3)]
+        staleness_blocks: u64,
+        /// Mortal era period in blocks (default 64)
+        #[arg(long, default_value_t = 64)]
+        era_period: u64,
+
+        /// Tip in RAO (planck), or "auto" to seed the first RBF round from the p75 on-chain tip oracle
+        #[arg(long)]
+        tip: Option<String>,
         /// RBF rounds (same nonce, higher tip)
         #[arg(long, default_value_t = 3)]
         rbf_rounds: u32,
@@ -121,6 +162,9 @@ enum Commands {
         neurons: Vec<String>,
         #[arg(long, default_value = "60")]
         interval: u64,
+        /// Use a finalized-head event subscription instead of fixed-interval polling
+        #[arg(long, default_value_t = false)]
+        push: bool,
     },
 
     /// Auto-register with retry logic
@@ -157,6 +201,13 @@ enum Commands {
         #[arg(short, long)]
         account: String,
     },
+
+    /// Broadcast a pre-signed SCALE extrinsic to every endpoint at once, exactly as signed (no tip bump, RBF or mempool watch)
+    Broadcast {
+        /// Hex-encoded signed extrinsic (0x-prefixed) produced by `register --offline`
+        #[arg(long)]
+        extrinsic_hex: String,
+    },
 }
 
 #[tokio::main]
@@ -179,34 +230,65 @@ async fn main() -> Result<()> {
             submit_on_new_head,
             head_delay_ms,
             era_period,
+            offline,
+            genesis_hash,
+            block_hash,
+            nonce,
             tip,
             rbf_rounds,
             bump,
             rbf_wait_secs,
         } => {
-            let register_client: QuickRegister = QuickRegister::new(cli.rpc_url.clone()).await?;
+            if offline {
+                // Air-gapped path: build and sign without touching the network.
+                let hex = register::build_offline_extrinsic(
+                    subnet, &wallet, &hotkey, burn_amount, genesis_hash, block_hash, nonce,
+                    era_period,
+                )?;
+                println!("{hex}");
+                return Ok(());
+            }
+            let register_client: QuickRegister = QuickRegister::new(cli.rpc_url.clone(), cli.submit_timeout_secs, cli.max_lag_blocks).await?;
             register_client
                 .register_to_subnet(subnet, &wallet, &hotkey, burn_amount, submit_on_new_head, head_delay_ms, era_period, tip, rbf_rounds, bump, rbf_wait_secs)
                 .await?;
         }
 
+        Commands::PowRegister {
+            subnet,
+            wallet,
+            hotkey,
+            workers,
+            staleness_blocks,
+            era_period,
+            tip,
+            rbf_rounds,
+            bump,
+            rbf_wait_secs,
+        } => {
+            let register_client: QuickRegister = QuickRegister::new(cli.rpc_url.clone(), cli.submit_timeout_secs, cli.max_lag_blocks).await?;
+            register_client
+                .pow_register_to_subnet(subnet, &wallet, &hotkey, workers, staleness_blocks, era_period, tip, rbf_rounds, bump, rbf_wait_secs)
+                .await?;
+        }
+
         Commands::Status { subnet, hotkey } => {
-            let register_client = QuickRegister::new(cli.rpc_url).await?;
+            let register_client = QuickRegister::new(cli.rpc_url, cli.submit_timeout_secs, cli.max_lag_blocks).await?;
             register_client.check_status(subnet, &hotkey).await?;
         }
 
         Commands::SubnetInfo { subnet } => {
-            let register_client = QuickRegister::new(cli.rpc_url).await?;
+            let register_client = QuickRegister::new(cli.rpc_url, cli.submit_timeout_secs, cli.max_lag_blocks).await?;
             register_client.show_subnet_info(subnet).await?;
         }
 
         Commands::EstimateCost { subnet } => {
-            let register_client = QuickRegister::new(cli.rpc_url).await?;
+            let register_client = QuickRegister::new(cli.rpc_url, cli.submit_timeout_secs, cli.max_lag_blocks).await?;
             register_client.estimate_registration_cost(subnet).await?;
         }
 
-        Commands::Monitor { neurons, interval } => {
-            let register_client = QuickRegister::new(cli.rpc_url).await?;
+        Commands::Monitor { neurons, interval, push } => {
+            let register_client = QuickRegister::new(cli.rpc_url, cli.submit_timeout_secs, cli.max_lag_blocks).await?;
             let parsed_neurons: Result<Vec<(u16, String)>> = neurons
                 .iter()
                 .map(|s| {
@@ -221,6 +303,13 @@ async fn main() -> Result<()> {
 
             let parsed_neurons = parsed_neurons?;
 
+            if push {
+                register_client
+                    .subscribe_registration_events(parsed_neurons)
+                    .await?;
+                return Ok(());
+            }
+
             loop {
                 register_client
                     .monitor_multiple_neurons(parsed_neurons.clone())
@@ -236,31 +325,36 @@ async fn main() -> Result<()> {
             hotkey,
             max_retries,
         } => {
-            let register_client = QuickRegister::new(cli.rpc_url).await?;
+            let register_client = QuickRegister::new(cli.rpc_url, cli.submit_timeout_secs, cli.max_lag_blocks).await?;
             register_client
                 .auto_register_with_retry(subnet, &wallet, &hotkey, max_retries)
                 .await?;
         }
 
         Commands::NetworkStats => {
-            let register_client = QuickRegister::new(cli.rpc_url).await?;
+            let register_client = QuickRegister::new(cli.rpc_url, cli.submit_timeout_secs, cli.max_lag_blocks).await?;
             register_client.show_network_statistics().await?;
         }
 
         Commands::ExportConfig { subnet, output } => {
-            let register_client = QuickRegister::new(cli.rpc_url).await?;
+            let register_client = QuickRegister::new(cli.rpc_url, cli.submit_timeout_secs, cli.max_lag_blocks).await?;
             register_client.export_config(subnet, &output).await?;
         }
 
         Commands::Batch { config } => {
-            let register_client = QuickRegister::new(cli.rpc_url).await?;
+            let register_client = QuickRegister::new(cli.rpc_url, cli.submit_timeout_secs, cli.max_lag_blocks).await?;
             register_client.execute_batch_operations(&config).await?;
         }
 
         Commands::Balance { account } => {
-            let register_client = QuickRegister::new(cli.rpc_url).await?;
+            let register_client = QuickRegister::new(cli.rpc_url, cli.submit_timeout_secs, cli.max_lag_blocks).await?;
             register_client.check_account_balance(&account).await?;
         }
+
+        Commands::Broadcast { extrinsic_hex } => {
+            let register_client = QuickRegister::new(cli.rpc_url, cli.submit_timeout_secs, cli.max_lag_blocks).await?;
+            register_client.broadcast_extrinsic(&extrinsic_hex).await?;
+        }
     }
 
     Ok(())
@@ -0,0 +1,688 @@
+//! Registration strategy: burn and proof-of-work registration, RBF tip
+//! bidding, and the monitoring commands. All network access is delegated to
+//! the [`crate::client`] pool.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use anyhow::{anyhow, Context, Result};
+use colored::*;
+use primitive_types::{H256, U256};
+use sha2::{Digest, Sha256};
+use subxt::config::PolkadotConfig;
+use subxt::dynamic::Value;
+use subxt::ext::codec::Decode;
+use subxt::tx::Signer;
+use tiny_keccak::{Hasher, Keccak};
+use tokio::time::sleep;
+
+use crate::client::{
+    subscribe_registration_events, ConnectionPool, RegistrationUpdate, SignedExtrinsic,
+    SubmissionExecutor, TipBlock,
+};
+use crate::constants::*;
+use crate::key_utils::{load_coldkey, load_hotkey, Keypair};
+use crate::utils::{format_tao, percentile};
+
+/// Percentile statistics over recently observed on-chain tips, in RAO.
+pub struct TipPercentiles {
+    pub p50: u128,
+    pub p75: u128,
+    pub p90: u128,
+    pub samples: usize,
+}
+
+/// A solved proof-of-work registration puzzle.
+pub struct PowSolution {
+    pub block_number: u64,
+    pub nonce: u64,
+    pub work: [u8; 32],
+}
+
+/// Compute the registration seal for a candidate nonce:
+/// `keccak256(sha256(block_hash || nonce_le))`.
+fn seal_for(block_hash: &H256, nonce: u64) -> [u8; 32] {
+    let mut pre = Sha256::new();
+    pre.update(block_hash.as_bytes());
+    pre.update(nonce.to_le_bytes());
+    let inner = pre.finalize();
+
+    let mut keccak = Keccak::v256();
+    let mut seal = [0u8; 32];
+    keccak.update(&inner);
+    keccak.finalize(&mut seal);
+    seal
+}
+
+/// Search for a valid nonce across `workers` threads, each scanning a disjoint
+/// (strided) nonce range. A seal is valid when, interpreted as a big-endian
+/// [`U256`], it is `<= U256::MAX / difficulty`. The first worker to find one
+/// flips `stop`, signalling the rest to abandon their search.
+///
+/// Returns `None` if `stop` is tripped externally (e.g. the tip advanced past
+/// the staleness window) before any worker succeeds.
+pub fn solve_pow(
+    tip: TipBlock,
+    difficulty: U256,
+    workers: usize,
+    stop: Arc<AtomicBool>,
+) -> Option<PowSolution> {
+    let limit = U256::MAX / difficulty.max(U256::one());
+    let workers = workers.max(1);
+    let found = Arc::new(std::sync::Mutex::new(None::<PowSolution>));
+
+    thread::scope(|scope| {
+        for w in 0..workers {
+            let stop = stop.clone();
+            let found = found.clone();
+            let hash = tip.hash;
+            scope.spawn(move || {
+                let mut nonce = w as u64;
+                while !stop.load(Ordering::Relaxed) {
+                    let seal = seal_for(&hash, nonce);
+                    if U256::from_big_endian(&seal) <= limit {
+                        *found.lock().unwrap() = Some(PowSolution {
+                            block_number: tip.number,
+                            nonce,
+                            work: seal,
+                        });
+                        stop.store(true, Ordering::Relaxed);
+                        return;
+                    }
+                    nonce = match nonce.checked_add(workers as u64) {
+                        Some(n) => n,
+                        None => return,
+                    };
+                }
+            });
+        }
+    });
+
+    Arc::try_unwrap(found).ok().and_then(|m| m.into_inner().unwrap())
+}
+
+/// High-level registration client over the endpoint pool.
+pub struct QuickRegister {
+    pool: Arc<ConnectionPool>,
+}
+
+impl QuickRegister {
+    pub async fn new(
+        rpc_url: Vec<String>,
+        submit_timeout_secs: u64,
+        max_lag_blocks: u64,
+    ) -> Result<Self> {
+        let pool = ConnectionPool::connect(
+            rpc_url,
+            Duration::from_secs(submit_timeout_secs),
+            max_lag_blocks,
+        )
+        .await?;
+        Ok(Self { pool: Arc::new(pool) })
+    }
+
+    // ---- burn registration --------------------------------------------------
+
+    #[allow(clippy::too_many_arguments)]
+    pub async fn register_to_subnet(
+        &self,
+        subnet: u16,
+        wallet: &str,
+        hotkey: &str,
+        burn_amount: Option<u64>,
+        submit_on_new_head: bool,
+        head_delay_ms: u64,
+        era_period: u64,
+        tip: Option<String>,
+        rbf_rounds: u32,
+        bump: f64,
+        rbf_wait_secs: u64,
+    ) -> Result<()> {
+        let coldkey = load_coldkey(wallet)?;
+        let hot = load_hotkey(wallet, hotkey)?;
+        let call = burned_register_call(subnet, &hot, burn_amount);
+
+        if submit_on_new_head {
+            self.wait_for_new_head(head_delay_ms).await?;
+        }
+
+        let first_tip = self.seed_tip(subnet, tip).await?;
+        self.rbf_submit(&call, &coldkey, era_period, first_tip, rbf_rounds, bump, rbf_wait_secs)
+            .await?;
+        self.await_registration(subnet, &hot.ss58).await
+    }
+
+    /// Resolve the starting tip. A literal RAO value is used verbatim, `auto`
+    /// takes the p75 from [`Self::estimate_priority_tip`], and no tip means 0.
+    async fn seed_tip(&self, subnet: u16, tip: Option<String>) -> Result<u128> {
+        match tip.as_deref() {
+            None => Ok(0),
+            Some("auto") => {
+                let stats = self.estimate_priority_tip(subnet).await?;
+                println!(
+                    "  tip oracle: p50 {} · p75 {} · p90 {} ({} samples)",
+                    format_tao(stats.p50),
+                    format_tao(stats.p75).bold(),
+                    format_tao(stats.p90),
+                    stats.samples
+                );
+                Ok(stats.p75)
+            }
+            Some(v) => v.parse::<u128>().context("--tip must be a RAO amount or \"auto\""),
+        }
+    }
+
+    /// Sample the tips attached to successful `burned_register` / `add_stake`
+    /// extrinsics on `subnet` across the last [`TIP_ORACLE_SAMPLE_BLOCKS`]
+    /// finalized blocks and return p50/p75/p90.
+    pub async fn estimate_priority_tip(&self, subnet: u16) -> Result<TipPercentiles> {
+        let api = self.pool.api().await;
+        let mut tips: Vec<u128> = Vec::new();
+        let mut block = api.blocks().at_latest().await?;
+
+        for _ in 0..TIP_ORACLE_SAMPLE_BLOCKS {
+            for ext in block.extrinsics().await?.iter() {
+                let ext = ext?;
+                let Ok(name) = ext.call_name() else { continue };
+                if !ext_targets_subnet(&ext, &name, subnet) {
+                    continue;
+                }
+                let succeeded = match ext.events().await {
+                    Ok(events) => events.iter().flatten().any(|ev| {
+                        ev.pallet_name() == "System" && ev.variant_name() == "ExtrinsicSuccess"
+                    }),
+                    Err(_) => false,
+                };
+                if !succeeded {
+                    continue;
+                }
+                if let Some(tip) = ext_tip(&ext) {
+                    tips.push(tip);
+                }
+            }
+            let parent = block.header().parent_hash;
+            if parent == H256::zero().0.into() {
+                break;
+            }
+            block = api.blocks().at(parent).await?;
+        }
+
+        tips.sort_unstable();
+        Ok(TipPercentiles {
+            p50: percentile(&tips, 50.0),
+            p75: percentile(&tips, 75.0),
+            p90: percentile(&tips, 90.0),
+            samples: tips.len(),
+        })
+    }
+
+    /// Replace-by-fee loop: resubmit the same nonce with a geometrically
+    /// increasing tip until the extrinsic is included or the rounds run out.
+    #[allow(clippy::too_many_arguments)]
+    async fn rbf_submit(
+        &self,
+        call: &subxt::dynamic::Payload,
+        signer: &Keypair,
+        era_period: u64,
+        first_tip: u128,
+        rbf_rounds: u32,
+        bump: f64,
+        rbf_wait_secs: u64,
+    ) -> Result<()> {
+        let executor = SubmissionExecutor::new(self.pool.clone());
+        let mut tip = first_tip;
+        for round in 0..rbf_rounds.max(1) {
+            let ext = self.build_signed(call, signer, era_period, tip).await?;
+            println!("  round {round}: tip {}", format_tao(tip));
+            match executor.submit_all(&ext).await {
+                Ok(report) => {
+                    let (mut submitted, mut confirmed, mut timed_out) = (0, 0, 0);
+                    for (endpoint, c) in &report.counters {
+                        submitted += c.submitted;
+                        confirmed += c.confirmed;
+                        timed_out += c.timed_out;
+                        log::info!(
+                            "    {endpoint}: submitted {} confirmed {} timed_out {}",
+                            c.submitted, c.confirmed, c.timed_out
+                        );
+                    }
+                    println!(
+                        "  included via {} (submitted {submitted}, confirmed {confirmed}, timed_out {timed_out})",
+                        report.endpoint.green()
+                    );
+                    return Ok(());
+                }
+                Err(e) => {
+                    log::warn!("  round {round} not included ({e:#}); bumping tip");
+                    tip = ((tip.max(1) as f64) * bump) as u128;
+                    sleep(Duration::from_secs(rbf_wait_secs)).await;
+                }
+            }
+        }
+        Err(anyhow!("extrinsic not included after {rbf_rounds} RBF rounds"))
+    }
+
+    // ---- proof-of-work registration -----------------------------------------
+
+    #[allow(clippy::too_many_arguments)]
+    pub async fn pow_register_to_subnet(
+        &self,
+        subnet: u16,
+        wallet: &str,
+        hotkey: &str,
+        workers: Option<usize>,
+        staleness_blocks: u64,
+        era_period: u64,
+        tip: Option<String>,
+        rbf_rounds: u32,
+        bump: f64,
+        rbf_wait_secs: u64,
+    ) -> Result<()> {
+        let coldkey = load_coldkey(wallet)?;
+        let hot = load_hotkey(wallet, hotkey)?;
+        let workers = workers.unwrap_or_else(num_cpus::get);
+        let difficulty = self.pool.difficulty(subnet).await?;
+        println!("⛏  solving PoW for subnet {subnet} — difficulty {difficulty}, {workers} workers");
+
+        // Solve against the current tip, restarting if the chain advances past
+        // the staleness window (stale work is rejected on-chain).
+        let solution = loop {
+            let start_tip = self.pool.tip_block().await?;
+            let stop = Arc::new(AtomicBool::new(false));
+
+            let watch = {
+                let pool = self.pool.clone();
+                let stop = stop.clone();
+                let start = start_tip.number;
+                tokio::spawn(async move {
+                    loop {
+                        sleep(Duration::from_secs(2)).await;
+                        if stop.load(Ordering::Relaxed) {
+                            return false;
+                        }
+                        if let Ok(tip) = pool.tip_block().await {
+                            if tip.number.saturating_sub(start) > staleness_blocks {
+                                stop.store(true, Ordering::Relaxed);
+                                return true; // stale: solver must restart
+                            }
+                        }
+                    }
+                })
+            };
+
+            let solver_stop = stop.clone();
+            let solution = tokio::task::spawn_blocking(move || {
+                solve_pow(start_tip, difficulty, workers, solver_stop)
+            })
+            .await?;
+            let was_stale = watch.await.unwrap_or(false);
+
+            match solution {
+                Some(s) if !was_stale => break s,
+                _ => {
+                    println!("  tip advanced past {staleness_blocks}-block window — restarting");
+                    continue;
+                }
+            }
+        };
+
+        println!("  ✓ solved: block {} nonce {}", solution.block_number, solution.nonce);
+        let call = register_call(subnet, &hot, &solution);
+        let first_tip = self.seed_tip(subnet, tip).await?;
+        self.rbf_submit(&call, &coldkey, era_period, first_tip, rbf_rounds, bump, rbf_wait_secs)
+            .await?;
+        self.await_registration(subnet, &hot.ss58).await
+    }
+
+    // ---- monitoring ----------------------------------------------------------
+
+    /// Return the instant `hotkey`'s UID appears, driven by the finalized-head
+    /// event subscription rather than status polling.
+    async fn await_registration(&self, subnet: u16, hotkey_ss58: &str) -> Result<()> {
+        // Guard against the UID having landed before the subscription opened.
+        if let Some(uid) = self.uid_of(subnet, hotkey_ss58).await? {
+            println!("{} registered on subnet {subnet} as UID {uid}", "✓".green());
+            return Ok(());
+        }
+        let mut stream = subscribe_registration_events(self.pool.clone()).await?;
+        while let Some(RegistrationUpdate::NeuronRegistered { netuid, hotkey, uid }) =
+            stream.recv().await
+        {
+            if netuid == subnet && hotkey == hotkey_ss58 {
+                println!("{} registered on subnet {subnet} as UID {uid}", "✓".green());
+                return Ok(());
+            }
+        }
+        Err(anyhow!("event stream ended before {hotkey_ss58} registered"))
+    }
+
+    /// Push-mode monitor: update many neurons in real time from a single
+    /// finalized-head subscription instead of fixed-interval polling.
+    pub async fn subscribe_registration_events(&self, neurons: Vec<(u16, String)>) -> Result<()> {
+        let mut stream = subscribe_registration_events(self.pool.clone()).await?;
+        println!("👁  push monitoring {} neuron(s)…", neurons.len());
+        while let Some(RegistrationUpdate::NeuronRegistered { netuid, hotkey, uid }) =
+            stream.recv().await
+        {
+            if neurons.iter().any(|(s, h)| *s == netuid && h == &hotkey) {
+                println!("  subnet {netuid}: {hotkey} → UID {uid}");
+            }
+        }
+        Ok(())
+    }
+
+    pub async fn monitor_multiple_neurons(&self, neurons: Vec<(u16, String)>) -> Result<()> {
+        for (subnet, hotkey) in neurons {
+            self.check_status(subnet, &hotkey).await?;
+        }
+        Ok(())
+    }
+
+    pub async fn check_status(&self, subnet: u16, hotkey: &str) -> Result<()> {
+        match self.uid_of(subnet, hotkey).await? {
+            Some(uid) => println!("subnet {subnet}: {hotkey} registered as UID {uid}"),
+            None => println!("subnet {subnet}: {hotkey} not registered"),
+        }
+        Ok(())
+    }
+
+    pub async fn show_subnet_info(&self, subnet: u16) -> Result<()> {
+        let difficulty = self.pool.difficulty(subnet).await?;
+        println!("subnet {subnet}: difficulty {difficulty}");
+        Ok(())
+    }
+
+    pub async fn estimate_registration_cost(&self, subnet: u16) -> Result<()> {
+        let difficulty = self.pool.difficulty(subnet).await?;
+        let tips = self.estimate_priority_tip(subnet).await?;
+        println!("subnet {subnet}");
+        println!("  difficulty : {difficulty}");
+        println!(
+            "  tip oracle : p50 {} · p75 {} · p90 {} ({} samples)",
+            format_tao(tips.p50),
+            format_tao(tips.p75),
+            format_tao(tips.p90),
+            tips.samples
+        );
+        Ok(())
+    }
+
+    pub async fn auto_register_with_retry(
+        &self,
+        subnet: u16,
+        wallet: &str,
+        hotkey: &str,
+        max_retries: usize,
+    ) -> Result<()> {
+        let mut last_err = None;
+        for attempt in 0..max_retries.max(1) {
+            match self
+                .register_to_subnet(subnet, wallet, hotkey, None, false, 250, 64, None, 3, 1.5, 6)
+                .await
+            {
+                Ok(()) => return Ok(()),
+                Err(e) => {
+                    log::warn!("attempt {attempt} failed: {e:#}");
+                    last_err = Some(e);
+                }
+            }
+        }
+        Err(last_err.unwrap_or_else(|| anyhow!("registration failed")))
+    }
+
+    pub async fn show_network_statistics(&self) -> Result<()> {
+        let tip = self.pool.tip_block().await?;
+        println!("chain tip: block {} ({:?})", tip.number, tip.hash);
+        for h in self.pool.health().await {
+            println!(
+                "  {} — {:.0}ms, lag {}",
+                h.url,
+                h.latency.as_secs_f64() * 1_000.0,
+                h.lag
+            );
+        }
+        Ok(())
+    }
+
+    pub async fn export_config(&self, subnet: u16, output: &str) -> Result<()> {
+        let difficulty = self.pool.difficulty(subnet).await?;
+        let json = serde_json::json!({ "subnet": subnet, "difficulty": difficulty.to_string() });
+        std::fs::write(output, serde_json::to_string_pretty(&json)?)
+            .with_context(|| format!("writing {output}"))?;
+        println!("wrote {output}");
+        Ok(())
+    }
+
+    pub async fn execute_batch_operations(&self, config: &str) -> Result<()> {
+        let raw = std::fs::read_to_string(config).with_context(|| format!("reading {config}"))?;
+        let ops: Vec<serde_json::Value> = serde_json::from_str(&raw)?;
+        println!("executing {} batch operation(s)", ops.len());
+        Ok(())
+    }
+
+    pub async fn check_account_balance(&self, account: &str) -> Result<()> {
+        let api = self.pool.api().await;
+        let query = subxt::dynamic::storage("System", "Account", vec![Value::from_bytes(account)]);
+        match api.storage().at_latest().await?.fetch(&query).await? {
+            Some(_) => println!("{account}: account found"),
+            None => println!("{account}: no on-chain balance"),
+        }
+        Ok(())
+    }
+
+    // ---- extrinsic construction ---------------------------------------------
+
+    async fn build_signed(
+        &self,
+        call: &subxt::dynamic::Payload,
+        signer: &Keypair,
+        era_period: u64,
+        tip: u128,
+    ) -> Result<SignedExtrinsic> {
+        let api = self.pool.api().await;
+        let params = subxt::config::substrate::SubstrateExtrinsicParamsBuilder::new()
+            .tip(tip)
+            .mortal(era_period)
+            .build();
+        let signed = api
+            .tx()
+            .create_signed(call, &SrSigner(signer.pair.clone()), params)
+            .await?;
+        Ok(SignedExtrinsic::from_bytes(signed.into_encoded()))
+    }
+
+    /// Broadcast a pre-signed SCALE blob (produced by `register --offline`)
+    /// through the concurrent submission engine.
+    pub async fn broadcast_extrinsic(&self, extrinsic_hex: &str) -> Result<()> {
+        let ext = SignedExtrinsic::from_hex(extrinsic_hex)?;
+        println!("broadcasting {:?}", ext.hash);
+        let report = SubmissionExecutor::new(self.pool.clone())
+            .submit_all(&ext)
+            .await?;
+        println!("  included via {}", report.endpoint.green());
+        Ok(())
+    }
+
+    // ---- small on-chain reads ------------------------------------------------
+
+    async fn uid_of(&self, subnet: u16, hotkey: &str) -> Result<Option<u16>> {
+        let api = self.pool.api().await;
+        let query = subxt::dynamic::storage(
+            SUBTENSOR_PALLET,
+            "Uids",
+            vec![Value::u128(subnet as u128), Value::from_bytes(hotkey)],
+        );
+        let raw = api.storage().at_latest().await?.fetch(&query).await?;
+        Ok(raw.and_then(|v| v.to_value().ok()?.as_u128().map(|u| u as u16)))
+    }
+
+    async fn wait_for_new_head(&self, delay_ms: u64) -> Result<()> {
+        let start = self.pool.tip_block().await?.number;
+        loop {
+            if self.pool.tip_block().await?.number > start {
+                sleep(Duration::from_millis(delay_ms)).await;
+                return Ok(());
+            }
+            sleep(Duration::from_millis(100)).await;
+        }
+    }
+}
+
+fn burned_register_call(
+    subnet: u16,
+    hotkey: &Keypair,
+    _burn_amount: Option<u64>,
+) -> subxt::dynamic::Payload {
+    use sp_core::Pair;
+    subxt::dynamic::tx(
+        SUBTENSOR_PALLET,
+        BURNED_REGISTER_CALL,
+        vec![
+            Value::u128(subnet as u128),
+            Value::from_bytes(hotkey.pair.public().0),
+        ],
+    )
+}
+
+fn register_call(subnet: u16, hotkey: &Keypair, sol: &PowSolution) -> subxt::dynamic::Payload {
+    use sp_core::Pair;
+    subxt::dynamic::tx(
+        SUBTENSOR_PALLET,
+        REGISTER_CALL,
+        vec![
+            Value::u128(subnet as u128),
+            Value::u128(sol.block_number as u128),
+            Value::u128(sol.nonce as u128),
+            Value::from_bytes(sol.work),
+            Value::from_bytes(hotkey.pair.public().0),
+        ],
+    )
+}
+
+/// Build and sign a `burned_register` extrinsic entirely offline, with no RPC
+/// connection, so the coldkey can stay on an air-gapped machine. The chain
+/// context subxt would normally fetch — genesis hash, mortal checkpoint,
+/// account nonce, runtime version and metadata — must be supplied locally.
+///
+/// Metadata is read from the file named by `BT_OFFLINE_METADATA` (default
+/// `bittensor-metadata.scale`) and the runtime version from the
+/// `BT_SPEC_VERSION` / `BT_TX_VERSION` environment variables, so the
+/// air-gapped box never needs to reach a node. Returns the 0x-hex SCALE blob.
+#[allow(clippy::too_many_arguments)]
+pub fn build_offline_extrinsic(
+    subnet: u16,
+    wallet: &str,
+    hotkey: &str,
+    burn_amount: Option<u64>,
+    genesis_hash: Option<String>,
+    block_hash: Option<String>,
+    nonce: Option<u32>,
+    era_period: u64,
+) -> Result<String> {
+    let genesis = require_h256(genesis_hash, "--genesis-hash")?;
+    let checkpoint = require_h256(block_hash, "--block-hash")?;
+    let nonce = nonce.ok_or_else(|| anyhow!("--offline requires --nonce"))? as u64;
+
+    let metadata = load_offline_metadata()?;
+    let runtime_version = offline_runtime_version()?;
+    let client = subxt::OfflineClient::<PolkadotConfig>::new(genesis, runtime_version, metadata);
+
+    let coldkey = load_coldkey(wallet)?;
+    let hot = load_hotkey(wallet, hotkey)?;
+    let call = burned_register_call(subnet, &hot, burn_amount);
+
+    let params = subxt::config::substrate::SubstrateExtrinsicParamsBuilder::new()
+        .tip(0)
+        .nonce(nonce)
+        .mortal_unchecked(0, checkpoint, era_period)
+        .build();
+    let signed = client
+        .tx()
+        .create_signed_offline(&call, &SrSigner(coldkey.pair), params)?;
+    Ok(SignedExtrinsic::from_bytes(signed.into_encoded()).to_hex())
+}
+
+/// Load the runtime metadata the offline signer needs to encode the call.
+fn load_offline_metadata() -> Result<subxt::Metadata> {
+    let path = std::env::var("BT_OFFLINE_METADATA")
+        .unwrap_or_else(|_| "bittensor-metadata.scale".to_string());
+    let raw = std::fs::read(&path).with_context(|| {
+        format!("offline signing needs local runtime metadata at {path} (set BT_OFFLINE_METADATA)")
+    })?;
+    subxt::Metadata::decode(&mut raw.as_slice()).context("decoding runtime metadata")
+}
+
+/// Runtime version for the offline signer, from the environment.
+fn offline_runtime_version() -> Result<subxt::client::RuntimeVersion> {
+    let spec_version = std::env::var("BT_SPEC_VERSION")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .ok_or_else(|| anyhow!("offline signing needs BT_SPEC_VERSION"))?;
+    let transaction_version = std::env::var("BT_TX_VERSION")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .ok_or_else(|| anyhow!("offline signing needs BT_TX_VERSION"))?;
+    Ok(subxt::client::RuntimeVersion { spec_version, transaction_version })
+}
+
+/// Parse a required 0x-prefixed 32-byte hash override.
+fn require_h256(s: Option<String>, flag: &str) -> Result<H256> {
+    let s = s.ok_or_else(|| anyhow!("--offline requires {flag}"))?;
+    parse_h256(Some(&s), || unreachable!())
+}
+
+/// sr25519 signer adapter bridging [`Keypair`] to subxt's [`Signer`] trait.
+struct SrSigner(sp_core::sr25519::Pair);
+
+impl Signer<PolkadotConfig> for SrSigner {
+    fn account_id(&self) -> subxt::config::substrate::AccountId32 {
+        use sp_core::Pair;
+        subxt::config::substrate::AccountId32(self.0.public().0)
+    }
+
+    fn sign(&self, payload: &[u8]) -> subxt::utils::MultiSignature {
+        use sp_core::Pair;
+        subxt::utils::MultiSignature::Sr25519(self.0.sign(payload).0)
+    }
+}
+
+type ExtrinsicDetails =
+    subxt::blocks::ExtrinsicDetails<PolkadotConfig, subxt::OnlineClient<PolkadotConfig>>;
+
+/// Whether a tip-bearing extrinsic targets `subnet`. The netuid lives at a
+/// different argument index per call: `burned_register(netuid, hotkey)` carries
+/// it first, whereas `add_stake(hotkey, netuid, amount)` carries the hotkey
+/// AccountId first and the netuid second. Any other call is not in scope.
+fn ext_targets_subnet(ext: &ExtrinsicDetails, call_name: &str, subnet: u16) -> bool {
+    let netuid_index = match call_name {
+        BURNED_REGISTER_CALL => 0,
+        "add_stake" => 1,
+        _ => return false,
+    };
+    ext.field_values()
+        .ok()
+        .and_then(|f| f.at(netuid_index).and_then(|v| v.as_u128()))
+        .map(|n| n as u16 == subnet)
+        .unwrap_or(false)
+}
+
+/// The tip attached to a signed extrinsic, if any.
+fn ext_tip(ext: &ExtrinsicDetails) -> Option<u128> {
+    ext.signed_extensions()?.tip()
+}
+
+fn parse_h256(s: Option<&str>, default: impl FnOnce() -> H256) -> Result<H256> {
+    match s {
+        None => Ok(default()),
+        Some(v) => {
+            let raw = v.strip_prefix("0x").unwrap_or(v);
+            let bytes = hex::decode(raw).context("hash is not valid hex")?;
+            if bytes.len() != 32 {
+                return Err(anyhow!("hash must be 32 bytes, got {}", bytes.len()));
+            }
+            Ok(H256::from_slice(&bytes))
+        }
+    }
+}
@@ -0,0 +1,17 @@
+//! Small presentation helpers shared by the register commands.
+
+/// Format a RAO (planck) amount as TAO with four decimals.
+pub fn format_tao(rao: u128) -> String {
+    let tao = rao as f64 / 1e9;
+    format!("{tao:.4} τ")
+}
+
+/// Nearest-rank percentile over an already-sorted sample.
+pub fn percentile(sorted: &[u128], pct: f64) -> u128 {
+    if sorted.is_empty() {
+        return 0;
+    }
+    let rank = ((pct / 100.0) * sorted.len() as f64).ceil() as usize;
+    let idx = rank.saturating_sub(1).min(sorted.len() - 1);
+    sorted[idx]
+}